@@ -0,0 +1,11 @@
+//! A small SemVer implementation: parsing, precedence ordering, and
+//! `node-semver`-style version ranges.
+
+mod req;
+#[cfg(feature = "serde")]
+mod serde_impl;
+mod util;
+mod version;
+
+pub use req::{Comparator, Op, ParseError as ReqParseError, VersionReq};
+pub use version::{Identifier, ParseError, Version};
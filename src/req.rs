@@ -0,0 +1,568 @@
+use std::fmt;
+use std::io::{self, BufRead, Cursor};
+use std::str::FromStr;
+
+use thiserror::Error;
+
+use crate::util::*;
+use crate::version::{self, Identifier, ParseMode, Version};
+
+// range-set  ::= range ( logical-or range ) *
+// logical-or ::= ( ' ' ) * '||' ( ' ' ) *
+// range      ::= hyphen | simple ( ' ' simple ) * | ''
+// hyphen     ::= partial ' - ' partial
+// simple     ::= primitive | partial | tilde | caret
+// primitive  ::= ( '<' | '>' | '>=' | '<=' | '=' ) partial
+// partial    ::= xr ( '.' xr ( '.' xr qualifier ? )? )?
+// xr         ::= 'x' | 'X' | '*' | nr
+// nr         ::= '0' | ['1'-'9'] ( ['0'-'9'] ) *
+// tilde      ::= '~' partial
+// caret      ::= '^' partial
+// qualifier  ::= ( '-' pre )? ( '+' build )?
+// pre        ::= parts
+// build      ::= parts
+// parts      ::= part ( '.' part ) *
+// part       ::= nr | [-0-9A-Za-z]+
+
+#[derive(Debug, Error)]
+pub enum ParseError {
+    #[error("Invalid input: {:?}", found)]
+    Invalid { found: Option<char> },
+    #[error("Invalid numeric range")]
+    InvalidNumericRange,
+    #[error("Unexpected end of input")]
+    UnexpectedEof,
+    #[error("invalid version in range: {0}")]
+    Version(#[from] version::ParseError),
+    #[error("IO")]
+    Io(#[from] io::Error),
+}
+
+/// The comparison operator of a single [`Comparator`].
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Op {
+    Exact,
+    Greater,
+    GreaterEq,
+    Less,
+    LessEq,
+}
+
+impl fmt::Display for Op {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        let s = match self {
+            Op::Exact => "=",
+            Op::Greater => ">",
+            Op::GreaterEq => ">=",
+            Op::Less => "<",
+            Op::LessEq => "<=",
+        };
+        f.write_str(s)
+    }
+}
+
+/// A single bound on a [`Version`], e.g. `>=1.2.3`.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Comparator {
+    pub op: Op,
+    pub version: Version,
+}
+
+impl fmt::Display for Comparator {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "{}{}", self.op, self.version)
+    }
+}
+
+impl Comparator {
+    fn matches(&self, version: &Version) -> bool {
+        match self.op {
+            Op::Exact => version == &self.version,
+            Op::Greater => version > &self.version,
+            Op::GreaterEq => version >= &self.version,
+            Op::Less => version < &self.version,
+            Op::LessEq => version <= &self.version,
+        }
+    }
+}
+
+/// A version range, e.g. `^1.2.3 || >=2.0.0 <3.0.0`.
+///
+/// A [`VersionReq`] is a set of comparator groups joined by `||`; a
+/// version matches the whole `VersionReq` if it matches at least one
+/// group, and matches a group if it satisfies every comparator in it.
+/// Sugar forms (`^`, `~`, hyphen ranges, `x`/`X`/`*` wildcards) are
+/// desugared into plain `>=`/`<`/`<=` comparators at parse time.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct VersionReq {
+    comparator_sets: Vec<Vec<Comparator>>,
+}
+
+impl VersionReq {
+    /// Returns whether `version` satisfies this range.
+    ///
+    /// A prerelease version (non-empty `prerelease`) only matches if some
+    /// comparator in the satisfied group explicitly names a prerelease at
+    /// the same `major.minor.patch` — otherwise unstable versions would
+    /// leak into ranges that only asked for stable releases.
+    ///
+    /// ```rust
+    /// use semver2::{Version, VersionReq};
+    ///
+    /// let req: VersionReq = "^1.2.3".parse().unwrap();
+    /// assert!(req.matches(&Version::new(1, 5, 0)));
+    /// assert!(!req.matches(&Version::new(2, 0, 0)));
+    ///
+    /// let req: VersionReq = ">=1.0.0".parse().unwrap();
+    /// assert!(!req.matches(&"1.2.3-rc.1".parse().unwrap()));
+    /// ```
+    pub fn matches(&self, version: &Version) -> bool {
+        self.comparator_sets
+            .iter()
+            .any(|set| set_matches(set, version))
+    }
+}
+
+/// Whether every comparator in `set` holds for `version`, honoring the
+/// exclude-prerelease-by-default rule.
+fn set_matches(set: &[Comparator], version: &Version) -> bool {
+    if !set.iter().all(|c| c.matches(version)) {
+        return false;
+    }
+
+    if version.prerelease.is_empty() {
+        return true;
+    }
+
+    set.iter().any(|c| {
+        !c.version.prerelease.is_empty()
+            && c.version.major == version.major
+            && c.version.minor == version.minor
+            && c.version.patch == version.patch
+    })
+}
+
+impl fmt::Display for VersionReq {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        for (i, set) in self.comparator_sets.iter().enumerate() {
+            if i > 0 {
+                write!(f, " || ")?;
+            }
+            for (j, comparator) in set.iter().enumerate() {
+                if j > 0 {
+                    write!(f, " ")?;
+                }
+                write!(f, "{}", comparator)?;
+            }
+        }
+        Ok(())
+    }
+}
+
+/// A partially specified version, as found in a range: any of `minor`,
+/// `patch` may be omitted or given as a wildcard (`x`, `X`, `*`).
+#[derive(Debug, Clone)]
+struct Partial {
+    major: Option<u64>,
+    minor: Option<u64>,
+    patch: Option<u64>,
+    prerelease: Vec<Identifier>,
+    build: Vec<Identifier>,
+}
+
+enum Simple {
+    Primitive(Op, Partial),
+    Bare(Partial),
+    Tilde(Partial),
+    Caret(Partial),
+}
+
+/// Parses a single `xr` component: a number, or a wildcard (`None`).
+fn parse_xr<R: BufRead>(mut s: R) -> Result<Option<u64>, ParseError> {
+    match peek1(&mut s) {
+        Some(b'x') | Some(b'X') | Some(b'*') => {
+            s.consume(1);
+            Ok(None)
+        }
+        Some(b) if b.is_ascii_digit() => {
+            let raw = take_string_while(&mut s, |b| b.is_ascii_digit())?;
+            raw.parse().map(Some).map_err(|_| ParseError::InvalidNumericRange)
+        }
+        found => Err(ParseError::Invalid {
+            found: found.map(|b| b as char),
+        }),
+    }
+}
+
+fn parse_partial<R: BufRead>(mut s: R) -> Result<Partial, ParseError> {
+    let mut partial = Partial {
+        major: parse_xr(&mut s)?,
+        minor: None,
+        patch: None,
+        prerelease: Vec::new(),
+        build: Vec::new(),
+    };
+    if partial.major.is_none() || peek1(&mut s) != Some(b'.') {
+        return Ok(partial);
+    }
+    s.consume(1);
+
+    partial.minor = parse_xr(&mut s)?;
+    if partial.minor.is_none() || peek1(&mut s) != Some(b'.') {
+        return Ok(partial);
+    }
+    s.consume(1);
+
+    partial.patch = parse_xr(&mut s)?;
+    if partial.patch.is_none() {
+        return Ok(partial);
+    }
+
+    match peek1(&mut s) {
+        Some(b'-') => {
+            s.consume(1);
+            partial.prerelease = version::parse_parts(&mut s, ParseMode::Loose)?;
+            if peek1(&mut s) == Some(b'+') {
+                s.consume(1);
+                partial.build = version::parse_parts(&mut s, ParseMode::Loose)?;
+            }
+        }
+        Some(b'+') => {
+            s.consume(1);
+            partial.build = version::parse_parts(&mut s, ParseMode::Loose)?;
+        }
+        _ => {}
+    }
+
+    Ok(partial)
+}
+
+fn parse_simple<R: BufRead>(mut s: R) -> Result<Simple, ParseError> {
+    match peek1(&mut s) {
+        Some(b'^') => {
+            s.consume(1);
+            Ok(Simple::Caret(parse_partial(&mut s)?))
+        }
+        Some(b'~') => {
+            s.consume(1);
+            Ok(Simple::Tilde(parse_partial(&mut s)?))
+        }
+        Some(b'>') => {
+            s.consume(1);
+            if peek1(&mut s) == Some(b'=') {
+                s.consume(1);
+                Ok(Simple::Primitive(Op::GreaterEq, parse_partial(&mut s)?))
+            } else {
+                Ok(Simple::Primitive(Op::Greater, parse_partial(&mut s)?))
+            }
+        }
+        Some(b'<') => {
+            s.consume(1);
+            if peek1(&mut s) == Some(b'=') {
+                s.consume(1);
+                Ok(Simple::Primitive(Op::LessEq, parse_partial(&mut s)?))
+            } else {
+                Ok(Simple::Primitive(Op::Less, parse_partial(&mut s)?))
+            }
+        }
+        Some(b'=') => {
+            s.consume(1);
+            Ok(Simple::Primitive(Op::Exact, parse_partial(&mut s)?))
+        }
+        _ => Ok(Simple::Bare(parse_partial(&mut s)?)),
+    }
+}
+
+fn comparator(op: Op, major: u64, minor: u64, patch: u64) -> Comparator {
+    Comparator {
+        op,
+        version: Version::new(major, minor, patch),
+    }
+}
+
+/// Desugars a bare partial/wildcard, e.g. `1.2.x` or `1.x`, into bounds.
+fn desugar_bare(partial: Partial) -> Vec<Comparator> {
+    match (partial.major, partial.minor, partial.patch) {
+        (Some(major), Some(minor), Some(patch)) => vec![Comparator {
+            op: Op::Exact,
+            version: Version {
+                major,
+                minor,
+                patch,
+                prerelease: partial.prerelease,
+                build: partial.build,
+            },
+        }],
+        (Some(major), Some(minor), None) => vec![
+            comparator(Op::GreaterEq, major, minor, 0),
+            comparator(Op::Less, major, minor + 1, 0),
+        ],
+        (Some(major), None, _) => vec![
+            comparator(Op::GreaterEq, major, 0, 0),
+            comparator(Op::Less, major + 1, 0, 0),
+        ],
+        (None, _, _) => vec![],
+    }
+}
+
+/// Desugars `~partial` into `>=`/`<` bounds.
+fn desugar_tilde(partial: Partial) -> Vec<Comparator> {
+    let major = partial.major.unwrap_or(0);
+    match (partial.minor, partial.patch) {
+        (Some(minor), Some(patch)) => vec![
+            Comparator {
+                op: Op::GreaterEq,
+                version: Version {
+                    major,
+                    minor,
+                    patch,
+                    prerelease: partial.prerelease,
+                    build: Vec::new(),
+                },
+            },
+            comparator(Op::Less, major, minor + 1, 0),
+        ],
+        (Some(minor), None) => vec![
+            comparator(Op::GreaterEq, major, minor, 0),
+            comparator(Op::Less, major, minor + 1, 0),
+        ],
+        (None, _) => vec![
+            comparator(Op::GreaterEq, major, 0, 0),
+            comparator(Op::Less, major + 1, 0, 0),
+        ],
+    }
+}
+
+/// Desugars `^partial` into `>=`/`<` bounds, holding the leftmost
+/// non-zero component fixed.
+fn desugar_caret(partial: Partial) -> Vec<Comparator> {
+    let major = partial.major.unwrap_or(0);
+    let minor = partial.minor.unwrap_or(0);
+    let patch = partial.patch.unwrap_or(0);
+
+    let lower = Comparator {
+        op: Op::GreaterEq,
+        version: Version {
+            major,
+            minor,
+            patch,
+            prerelease: partial.prerelease,
+            build: Vec::new(),
+        },
+    };
+
+    let upper = if major > 0 {
+        comparator(Op::Less, major + 1, 0, 0)
+    } else if minor > 0 {
+        comparator(Op::Less, 0, minor + 1, 0)
+    } else if partial.patch.is_some() {
+        comparator(Op::Less, 0, 0, patch + 1)
+    } else if partial.minor.is_some() {
+        comparator(Op::Less, 0, 1, 0)
+    } else {
+        comparator(Op::Less, 1, 0, 0)
+    };
+
+    vec![lower, upper]
+}
+
+fn desugar(simple: Simple) -> Vec<Comparator> {
+    match simple {
+        Simple::Primitive(op, partial) => vec![Comparator {
+            op,
+            version: Version {
+                major: partial.major.unwrap_or(0),
+                minor: partial.minor.unwrap_or(0),
+                patch: partial.patch.unwrap_or(0),
+                prerelease: partial.prerelease,
+                build: partial.build,
+            },
+        }],
+        Simple::Bare(partial) => desugar_bare(partial),
+        Simple::Tilde(partial) => desugar_tilde(partial),
+        Simple::Caret(partial) => desugar_caret(partial),
+    }
+}
+
+fn partial_from_str(s: &str) -> Result<Partial, ParseError> {
+    let mut cursor = Cursor::new(s);
+    let partial = parse_partial(&mut cursor)?;
+    if !is_eof(&mut cursor) {
+        return Err(ParseError::Invalid {
+            found: peek1(&mut cursor).map(|b| b as char),
+        });
+    }
+    Ok(partial)
+}
+
+/// Desugars a hyphen range `lower - upper` into a `>=` lower bound and a
+/// `<=` upper bound; a partial `upper` (e.g. `2.3` or `2`) instead gives a
+/// `<` bound on the next minor or major version.
+fn parse_hyphen(lower: &str, upper: &str) -> Result<Vec<Comparator>, ParseError> {
+    let lower = partial_from_str(lower)?;
+    let upper = partial_from_str(upper)?;
+
+    let lower_comparator = Comparator {
+        op: Op::GreaterEq,
+        version: Version {
+            major: lower.major.unwrap_or(0),
+            minor: lower.minor.unwrap_or(0),
+            patch: lower.patch.unwrap_or(0),
+            prerelease: lower.prerelease,
+            build: Vec::new(),
+        },
+    };
+
+    let upper_comparator = match (upper.major, upper.minor, upper.patch) {
+        (Some(major), Some(minor), Some(patch)) => Comparator {
+            op: Op::LessEq,
+            version: Version {
+                major,
+                minor,
+                patch,
+                prerelease: upper.prerelease,
+                build: Vec::new(),
+            },
+        },
+        (Some(major), Some(minor), None) => comparator(Op::Less, major, minor + 1, 0),
+        (Some(major), None, _) => comparator(Op::Less, major + 1, 0, 0),
+        (None, _, _) => return Err(ParseError::UnexpectedEof),
+    };
+
+    Ok(vec![lower_comparator, upper_comparator])
+}
+
+fn parse_range(range: &str) -> Result<Vec<Comparator>, ParseError> {
+    if range.is_empty() {
+        return Ok(Vec::new());
+    }
+
+    if let Some(idx) = range.find(" - ") {
+        let (lower, rest) = range.split_at(idx);
+        return parse_hyphen(lower.trim(), rest[3..].trim());
+    }
+
+    let mut comparators = Vec::new();
+    for token in range.split_whitespace() {
+        let mut cursor = Cursor::new(token);
+        let simple = parse_simple(&mut cursor)?;
+        if !is_eof(&mut cursor) {
+            return Err(ParseError::Invalid {
+                found: peek1(&mut cursor).map(|b| b as char),
+            });
+        }
+        comparators.extend(desugar(simple));
+    }
+    Ok(comparators)
+}
+
+impl FromStr for VersionReq {
+    type Err = ParseError;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        let mut comparator_sets = Vec::new();
+        for range in s.split("||") {
+            comparator_sets.push(parse_range(range.trim())?);
+        }
+        Ok(VersionReq { comparator_sets })
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn v(major: u64, minor: u64, patch: u64) -> Version {
+        Version::new(major, minor, patch)
+    }
+
+    #[test]
+    fn exact() {
+        let req: VersionReq = "1.2.3".parse().unwrap();
+        assert!(req.matches(&v(1, 2, 3)));
+        assert!(!req.matches(&v(1, 2, 4)));
+    }
+
+    #[test]
+    fn primitive_comparators() {
+        let req: VersionReq = ">=1.2.3 <2.0.0".parse().unwrap();
+        assert!(req.matches(&v(1, 2, 3)));
+        assert!(req.matches(&v(1, 9, 9)));
+        assert!(!req.matches(&v(1, 2, 2)));
+        assert!(!req.matches(&v(2, 0, 0)));
+    }
+
+    #[test]
+    fn caret() {
+        let req: VersionReq = "^1.2.3".parse().unwrap();
+        assert!(req.matches(&v(1, 2, 3)));
+        assert!(req.matches(&v(1, 9, 0)));
+        assert!(!req.matches(&v(2, 0, 0)));
+
+        let req: VersionReq = "^0.2.3".parse().unwrap();
+        assert!(req.matches(&v(0, 2, 3)));
+        assert!(!req.matches(&v(0, 3, 0)));
+
+        let req: VersionReq = "^0.0.3".parse().unwrap();
+        assert!(req.matches(&v(0, 0, 3)));
+        assert!(!req.matches(&v(0, 0, 4)));
+    }
+
+    #[test]
+    fn tilde() {
+        let req: VersionReq = "~1.2.3".parse().unwrap();
+        assert!(req.matches(&v(1, 2, 9)));
+        assert!(!req.matches(&v(1, 3, 0)));
+
+        let req: VersionReq = "~1.2".parse().unwrap();
+        assert!(req.matches(&v(1, 2, 9)));
+        assert!(!req.matches(&v(1, 3, 0)));
+
+        let req: VersionReq = "~1".parse().unwrap();
+        assert!(req.matches(&v(1, 9, 9)));
+        assert!(!req.matches(&v(2, 0, 0)));
+    }
+
+    #[test]
+    fn wildcard() {
+        let req: VersionReq = "1.2.x".parse().unwrap();
+        assert!(req.matches(&v(1, 2, 0)));
+        assert!(req.matches(&v(1, 2, 9)));
+        assert!(!req.matches(&v(1, 3, 0)));
+
+        let req: VersionReq = "1.x".parse().unwrap();
+        assert!(req.matches(&v(1, 9, 9)));
+        assert!(!req.matches(&v(2, 0, 0)));
+
+        let req: VersionReq = "1.2.*".parse().unwrap();
+        assert!(req.matches(&v(1, 2, 5)));
+    }
+
+    #[test]
+    fn hyphen() {
+        let req: VersionReq = "1.2.3 - 2.3.4".parse().unwrap();
+        assert!(req.matches(&v(1, 2, 3)));
+        assert!(req.matches(&v(2, 3, 4)));
+        assert!(!req.matches(&v(2, 3, 5)));
+        assert!(!req.matches(&v(1, 2, 2)));
+    }
+
+    #[test]
+    fn prerelease_excluded_by_default() {
+        let req: VersionReq = ">=1.0.0".parse().unwrap();
+        assert!(!req.matches(&"1.2.3-rc.1".parse().unwrap()));
+        assert!(req.matches(&"1.2.3".parse().unwrap()));
+
+        let req: VersionReq = ">=1.2.3-alpha".parse().unwrap();
+        assert!(req.matches(&"1.2.3-beta".parse().unwrap()));
+        assert!(!req.matches(&"1.2.4-beta".parse().unwrap()));
+    }
+
+    #[test]
+    fn logical_or() {
+        let req: VersionReq = "1.2.3 || 2.3.4".parse().unwrap();
+        assert!(req.matches(&v(1, 2, 3)));
+        assert!(req.matches(&v(2, 3, 4)));
+        assert!(!req.matches(&v(1, 2, 4)));
+    }
+}
@@ -0,0 +1,103 @@
+//! `serde` support, gated behind the `serde` feature.
+//!
+//! Both [`Version`] and [`Identifier`] serialize to, and deserialize from,
+//! their canonical string form (the same one produced by `Display` and
+//! parsed back through `FromStr`), rather than exposing their internal
+//! fields. This lets either type drop straight into config files,
+//! lockfiles, and JSON APIs.
+
+use std::fmt;
+use std::str::FromStr;
+
+use serde::de::{self, Visitor};
+use serde::{Deserialize, Deserializer, Serialize, Serializer};
+
+use crate::{Identifier, Version};
+
+impl Serialize for Version {
+    fn serialize<S>(&self, serializer: S) -> Result<S::Ok, S::Error>
+    where
+        S: Serializer,
+    {
+        serializer.collect_str(self)
+    }
+}
+
+struct VersionVisitor;
+
+impl<'de> Visitor<'de> for VersionVisitor {
+    type Value = Version;
+
+    fn expecting(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.write_str("a semver version string")
+    }
+
+    fn visit_str<E>(self, v: &str) -> Result<Self::Value, E>
+    where
+        E: de::Error,
+    {
+        Version::from_str(v).map_err(de::Error::custom)
+    }
+}
+
+impl<'de> Deserialize<'de> for Version {
+    fn deserialize<D>(deserializer: D) -> Result<Self, D::Error>
+    where
+        D: Deserializer<'de>,
+    {
+        deserializer.deserialize_str(VersionVisitor)
+    }
+}
+
+impl Serialize for Identifier {
+    fn serialize<S>(&self, serializer: S) -> Result<S::Ok, S::Error>
+    where
+        S: Serializer,
+    {
+        serializer.collect_str(self)
+    }
+}
+
+struct IdentifierVisitor;
+
+impl<'de> Visitor<'de> for IdentifierVisitor {
+    type Value = Identifier;
+
+    fn expecting(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.write_str("a semver prerelease/build identifier")
+    }
+
+    fn visit_str<E>(self, v: &str) -> Result<Self::Value, E>
+    where
+        E: de::Error,
+    {
+        Identifier::from_str(v).map_err(de::Error::custom)
+    }
+}
+
+impl<'de> Deserialize<'de> for Identifier {
+    fn deserialize<D>(deserializer: D) -> Result<Self, D::Error>
+    where
+        D: Deserializer<'de>,
+    {
+        deserializer.deserialize_str(IdentifierVisitor)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn version_roundtrip() {
+        let version = Version::new_prerelease(1, 2, 3, vec!["alpha".parse().unwrap(), 9.into()]);
+        let json = serde_json::to_string(&version).unwrap();
+        assert_eq!(json, "\"1.2.3-alpha.9\"");
+        assert_eq!(serde_json::from_str::<Version>(&json).unwrap(), version);
+    }
+
+    #[test]
+    fn version_rejects_invalid() {
+        assert!(serde_json::from_str::<Version>("\"not a version\"").is_err());
+    }
+}
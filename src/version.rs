@@ -1,3 +1,4 @@
+use std::cmp::Ordering;
 use std::fmt;
 use std::io::{self, BufRead, Cursor};
 use std::str::FromStr;
@@ -50,7 +51,10 @@ impl fmt::Display for Version {
 }
 
 /// This the invidual parts in the string `beta.9`, separated by `.`.
-#[derive(Debug, Clone, PartialEq, Eq)]
+///
+/// A `Number` always has lower precedence than a `String`, per the
+/// declaration order of this enum's variants.
+#[derive(Debug, Clone, PartialEq, Eq, PartialOrd, Ord)]
 pub enum Identifier {
     Number(u64),
     /// Only ASCII symbols.
@@ -141,24 +145,133 @@ impl Version {
             build,
         }
     }
+
+    /// Compares for precedence equality, as defined by the SemVer spec.
+    ///
+    /// Unlike `==`, this ignores `build` metadata entirely, matching the
+    /// ordering implemented by `Ord`.
+    ///
+    /// ```rust
+    /// use semver2::Version;
+    ///
+    /// assert!(Version::new_build(1, 2, 3, vec!["a".parse().unwrap()])
+    ///     .eq_precedence(&Version::new_build(1, 2, 3, vec!["b".parse().unwrap()])));
+    /// ```
+    pub fn eq_precedence(&self, other: &Version) -> bool {
+        self.cmp(other) == Ordering::Equal
+    }
+
+    /// Bumps `major` by one, resetting `minor` and `patch` to `0` and
+    /// clearing `prerelease`/`build`, since the result is a fresh release.
+    ///
+    /// ```rust
+    /// use semver2::Version;
+    ///
+    /// let mut version = Version::new_prerelease(1, 2, 3, vec!["alpha".parse().unwrap()]);
+    /// version.increment_major();
+    /// assert_eq!(version, Version::new(2, 0, 0));
+    /// ```
+    pub fn increment_major(&mut self) -> &mut Self {
+        self.major += 1;
+        self.minor = 0;
+        self.patch = 0;
+        self.prerelease.clear();
+        self.build.clear();
+        self
+    }
+
+    /// Bumps `minor` by one, resetting `patch` to `0` and clearing
+    /// `prerelease`/`build`, since the result is a fresh release.
+    ///
+    /// ```rust
+    /// use semver2::Version;
+    ///
+    /// let mut version = Version::new_prerelease(1, 2, 3, vec!["alpha".parse().unwrap()]);
+    /// version.increment_minor();
+    /// assert_eq!(version, Version::new(1, 3, 0));
+    /// ```
+    pub fn increment_minor(&mut self) -> &mut Self {
+        self.minor += 1;
+        self.patch = 0;
+        self.prerelease.clear();
+        self.build.clear();
+        self
+    }
+
+    /// Bumps `patch` by one, clearing `prerelease`/`build`, since the
+    /// result is a fresh release.
+    ///
+    /// ```rust
+    /// use semver2::Version;
+    ///
+    /// let mut version = Version::new_prerelease(1, 2, 3, vec!["alpha".parse().unwrap()]);
+    /// version.increment_patch();
+    /// assert_eq!(version, Version::new(1, 2, 4));
+    /// ```
+    pub fn increment_patch(&mut self) -> &mut Self {
+        self.patch += 1;
+        self.prerelease.clear();
+        self.build.clear();
+        self
+    }
+
+    /// Sets (or, given an empty `Vec`, strips) the prerelease identifiers
+    /// in one call.
+    ///
+    /// ```rust
+    /// use semver2::Version;
+    ///
+    /// let mut version = Version::new_prerelease(1, 2, 3, vec!["alpha".parse().unwrap()]);
+    /// version.set_prerelease(Vec::new());
+    /// assert_eq!(version, Version::new(1, 2, 3));
+    /// ```
+    pub fn set_prerelease(&mut self, prerelease: Vec<Identifier>) -> &mut Self {
+        self.prerelease = prerelease;
+        self
+    }
+
+    /// Sets (or, given an empty `Vec`, strips) the build identifiers in
+    /// one call.
+    ///
+    /// ```rust
+    /// use semver2::Version;
+    ///
+    /// let mut version = Version::new_build(1, 2, 3, vec!["githash".parse().unwrap()]);
+    /// version.set_build(Vec::new());
+    /// assert_eq!(version, Version::new(1, 2, 3));
+    /// ```
+    pub fn set_build(&mut self, build: Vec<Identifier>) -> &mut Self {
+        self.build = build;
+        self
+    }
+}
+
+impl PartialOrd for Version {
+    fn partial_cmp(&self, other: &Self) -> Option<Ordering> {
+        Some(self.cmp(other))
+    }
 }
 
-// range-set  ::= range ( logical-or range ) *
-// logical-or ::= ( ' ' ) * '||' ( ' ' ) *
-// range      ::= hyphen | simple ( ' ' simple ) * | ''
-// hyphen     ::= partial ' - ' partial
-// simple     ::= primitive | partial | tilde | caret
-// primitive  ::= ( '<' | '>' | '>=' | '<=' | '=' ) partial
-// partial    ::= xr ( '.' xr ( '.' xr qualifier ? )? )?
-// xr         ::= 'x' | 'X' | '*' | nr
-// nr         ::= '0' | ['1'-'9'] ( ['0'-'9'] ) *
-// tilde      ::= '~' partial
-// caret      ::= '^' partial
-// qualifier  ::= ( '-' pre )? ( '+' build )?
-// pre        ::= parts
-// build      ::= parts
-// parts      ::= part ( '.' part ) *
-// part       ::= nr | [-0-9A-Za-z]+
+impl Ord for Version {
+    /// Orders by SemVer precedence: `major.minor.patch` numerically, then
+    /// `prerelease` identifiers left-to-right (no prerelease ranks higher
+    /// than any prerelease). `build` metadata is entirely ignored, per the
+    /// spec.
+    fn cmp(&self, other: &Self) -> Ordering {
+        self.major
+            .cmp(&other.major)
+            .then_with(|| self.minor.cmp(&other.minor))
+            .then_with(|| self.patch.cmp(&other.patch))
+            .then_with(
+                || match (self.prerelease.is_empty(), other.prerelease.is_empty()) {
+                    (true, true) => Ordering::Equal,
+                    (true, false) => Ordering::Greater,
+                    (false, true) => Ordering::Less,
+                    (false, false) => self.prerelease.cmp(&other.prerelease),
+                },
+            )
+    }
+}
 
 #[derive(Debug, Error)]
 pub enum ParseError {
@@ -168,32 +281,65 @@ pub enum ParseError {
     InvalidNumericRange,
     #[error("Unexpected end of input")]
     UnexpectedEof,
+    #[error("leading zeros are not allowed: {:?}", found)]
+    LeadingZero { found: String },
     #[error("IO")]
     Io(#[from] io::Error),
 }
 
+/// Whether a `Version` is parsed leniently (the `FromStr` default) or per
+/// the strict SemVer grammar (`Version::parse_strict`).
+///
+/// In `Strict` mode, `major`/`minor`/`patch` reject leading zeros, a
+/// numeric-looking prerelease/build identifier with a leading zero (e.g.
+/// `01`) is kept as an `Identifier::String` instead of a number, and a
+/// missing `-`/`+` separator before trailing characters is an error
+/// rather than being coerced into a prerelease.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub(crate) enum ParseMode {
+    Loose,
+    Strict,
+}
+
 /// Parses any sequence of digits.
-fn parse_numeric_range_loose<R: BufRead>(s: R) -> Result<u64, ParseError> {
+fn parse_numeric_range<R: BufRead>(s: R, mode: ParseMode) -> Result<u64, ParseError> {
     let raw = take_string_while(s, |b| b.is_ascii_digit())?;
+    if mode == ParseMode::Strict && raw.len() > 1 && raw.starts_with('0') {
+        return Err(ParseError::LeadingZero { found: raw });
+    }
     raw.parse().map_err(|_| ParseError::InvalidNumericRange)
 }
 
-fn parse_part<R: BufRead>(mut s: R) -> Result<Identifier, ParseError> {
+/// Parses a single dot-separated prerelease/build identifier.
+///
+/// Called by `parse_parts`, which the `req` module reuses (always in
+/// `Loose` mode) when parsing the qualifier of a range partial.
+pub(crate) fn parse_part<R: BufRead>(mut s: R, mode: ParseMode) -> Result<Identifier, ParseError> {
     let part = take_string_while(&mut s, |b| b.is_ascii_alphanumeric())?;
+    let looks_numeric = !part.is_empty() && part.bytes().all(|b| b.is_ascii_digit());
+    if mode == ParseMode::Strict && looks_numeric && part.len() > 1 && part.starts_with('0') {
+        // A numeric-looking identifier with a leading zero, e.g. `01`, is
+        // not a valid number per the spec and stays a string identifier.
+        return Ok(Identifier::String(part));
+    }
     match part.parse::<u64>() {
         Ok(number) => Ok(number.into()),
         _ => Ok(Identifier::String(part)),
     }
 }
 
-fn parse_parts<R: BufRead>(mut s: R) -> Result<Vec<Identifier>, ParseError> {
+/// Parses a dot-separated run of identifiers, e.g. `alpha.9`.
+pub(crate) fn parse_parts<R: BufRead>(
+    mut s: R,
+    mode: ParseMode,
+) -> Result<Vec<Identifier>, ParseError> {
     let mut res = Vec::new();
     loop {
         if is_eof(&mut s) {
             break;
         }
 
-        res.push(parse_part(&mut s)?);
+        res.push(parse_part(&mut s, mode)?);
 
         let next = peek1(&mut s);
         if next == Some(b'.') {
@@ -216,7 +362,28 @@ impl FromStr for Identifier {
     fn from_str(s: &str) -> Result<Self, Self::Err> {
         let mut s = Cursor::new(s);
 
-        parse_part(&mut s)
+        parse_part(&mut s, ParseMode::Loose)
+    }
+}
+
+impl Version {
+    /// Parses `s` per the strict SemVer grammar.
+    ///
+    /// Unlike the lenient `FromStr` impl, this rejects leading zeros in
+    /// `major`/`minor`/`patch`, keeps a numeric-looking prerelease/build
+    /// identifier with a leading zero (e.g. `01`) as a string identifier
+    /// rather than the number it would collapse to, and does not coerce a
+    /// missing `-`/`+` separator into an implicit prerelease.
+    ///
+    /// ```rust
+    /// use semver2::Version;
+    ///
+    /// assert!(Version::parse_strict("1.2.3").is_ok());
+    /// assert!(Version::parse_strict("01.2.3").is_err());
+    /// assert!(Version::parse_strict("1.2.3foo").is_err());
+    /// ```
+    pub fn parse_strict(s: &str) -> Result<Self, ParseError> {
+        parse_version(Cursor::new(s), ParseMode::Strict)
     }
 }
 
@@ -224,66 +391,72 @@ impl FromStr for Version {
     type Err = ParseError;
 
     fn from_str(s: &str) -> Result<Self, Self::Err> {
-        let mut s = Cursor::new(s);
+        parse_version(Cursor::new(s), ParseMode::Loose)
+    }
+}
 
-        let mut version = Version::default();
+fn parse_version<R: BufRead>(mut s: R, mode: ParseMode) -> Result<Version, ParseError> {
+    let mut version = Version::default();
 
-        // Major
-        version.major = parse_numeric_range_loose(&mut s)?;
-        if is_eof(&mut s) {
-            return Ok(version);
-        }
+    // Major
+    version.major = parse_numeric_range(&mut s, mode)?;
+    if is_eof(&mut s) {
+        return Ok(version);
+    }
 
-        // .
-        let next = take1(&mut s).map(|s| s as char);
-        if next != Some('.') {
-            return Err(ParseError::Invalid { found: next });
-        }
+    // .
+    let next = take1(&mut s).map(|s| s as char);
+    if next != Some('.') {
+        return Err(ParseError::Invalid { found: next });
+    }
 
-        // Minor (optional)
-        version.minor = parse_numeric_range_loose(&mut s)?;
-        if is_eof(&mut s) {
-            return Ok(version);
-        }
+    // Minor (optional)
+    version.minor = parse_numeric_range(&mut s, mode)?;
+    if is_eof(&mut s) {
+        return Ok(version);
+    }
 
-        // .
-        let next = take1(&mut s).map(|s| s as char);
-        if next != Some('.') {
-            return Err(ParseError::Invalid { found: next });
-        }
+    // .
+    let next = take1(&mut s).map(|s| s as char);
+    if next != Some('.') {
+        return Err(ParseError::Invalid { found: next });
+    }
+
+    // Patch (optional)
+    version.patch = parse_numeric_range(&mut s, mode)?;
+    if is_eof(&mut s) {
+        return Ok(version);
+    }
+
+    let mut next = peek1(&mut s).map(|s| s as char);
+    if next == Some('+') || next == Some('-') {
+        s.consume(1);
+    } else if mode == ParseMode::Strict && next.is_some() {
+        // Loose mode interprets `1.2.3foo` as `1.2.3-foo`; strict mode
+        // requires the explicit separator.
+        return Err(ParseError::Invalid { found: next });
+    }
 
-        // Patch (optional)
-        version.patch = parse_numeric_range_loose(&mut s)?;
+    // prerelease (optional)
+    // interpret 1.2.3foo as 1.2.3-foo (loose mode only, see above)
+    if next.is_some() && next != Some('+') {
+        version.prerelease = parse_parts(&mut s, mode)?;
         if is_eof(&mut s) {
             return Ok(version);
         }
 
-        let mut next = peek1(&mut s).map(|s| s as char);
-        if next == Some('+') || next == Some('-') {
-            s.consume(1);
-        }
-
-        // prerelease (optional)
-        // interpret 1.2.3foo as 1.2.3-foo
-        if next.is_some() && next != Some('+') {
-            version.prerelease = parse_parts(&mut s)?;
-            if is_eof(&mut s) {
-                return Ok(version);
-            }
-
-            // read the next part, as we consumed our next.
-            next = take1(&mut s).map(|s| s as char);
-        }
+        // read the next part, as we consumed our next.
+        next = take1(&mut s).map(|s| s as char);
+    }
 
-        // build (optional)
-        if next == Some('+') {
-            version.build = parse_parts(&mut s)?;
-            if is_eof(&mut s) {
-                return Ok(version);
-            }
+    // build (optional)
+    if next == Some('+') {
+        version.build = parse_parts(&mut s, mode)?;
+        if is_eof(&mut s) {
+            return Ok(version);
         }
-        Err(ParseError::Invalid { found: next })
     }
+    Err(ParseError::Invalid { found: next })
 }
 
 #[cfg(test)]
@@ -360,4 +533,118 @@ mod tests {
             Version::new_prerelease(1, 2, 3, vec!["foo".parse().unwrap(), 8.into()])
         );
     }
+
+    #[test]
+    fn strict_rejects_leading_zeros() {
+        assert!(Version::parse_strict("01.2.3").is_err());
+        assert!(Version::parse_strict("1.02.3").is_err());
+        assert!(Version::parse_strict("1.2.03").is_err());
+
+        assert_eq!(
+            Version::parse_strict("1.2.3").unwrap(),
+            Version::new(1, 2, 3)
+        );
+    }
+
+    #[test]
+    fn strict_rejects_implicit_prerelease() {
+        assert!(Version::parse_strict("1.2.3foo").is_err());
+        assert_eq!(
+            Version::parse_strict("1.2.3-foo").unwrap(),
+            Version::new_prerelease(1, 2, 3, vec!["foo".parse().unwrap()])
+        );
+    }
+
+    #[test]
+    fn strict_keeps_leading_zero_numeric_as_string() {
+        // `01` itself is a valid alphanumeric identifier, but its
+        // leading zero disqualifies it as a numeric identifier, so in
+        // strict mode it stays an `Identifier::String` rather than
+        // collapsing to the number `1`.
+        assert_eq!(
+            Version::parse_strict("1.2.3-01").unwrap(),
+            Version::new_prerelease(1, 2, 3, vec![Identifier::String("01".into())])
+        );
+    }
+
+    #[test]
+    fn increment_helpers() {
+        let mut version = Version::new_build(1, 2, 3, vec!["githash".parse().unwrap()]);
+        version.increment_patch();
+        assert_eq!(version, Version::new(1, 2, 4));
+
+        let mut version =
+            Version::new_prerelease(1, 2, 3, vec!["alpha".parse().unwrap()]);
+        version.increment_minor();
+        assert_eq!(version, Version::new(1, 3, 0));
+
+        let mut version =
+            Version::new_prerelease(1, 2, 3, vec!["alpha".parse().unwrap()]);
+        version.increment_major();
+        assert_eq!(version, Version::new(2, 0, 0));
+
+        let mut version = Version::new_prerelease(1, 2, 3, vec!["alpha".parse().unwrap()]);
+        version.set_prerelease(Vec::new());
+        assert_eq!(version, Version::new(1, 2, 3));
+
+        let mut version = Version::new(1, 2, 3);
+        version.set_build(vec!["githash".parse().unwrap()]);
+        assert_eq!(
+            version,
+            Version::new_build(1, 2, 3, vec!["githash".parse().unwrap()])
+        );
+    }
+
+    #[test]
+    fn identifier_ordering() {
+        assert!(Identifier::Number(9) < Identifier::String("1".into()));
+        assert!(Identifier::Number(1) < Identifier::Number(2));
+        assert!(Identifier::String("alpha".into()) < Identifier::String("beta".into()));
+    }
+
+    #[test]
+    fn version_ordering() {
+        assert!(Version::new(1, 0, 0) < Version::new(2, 0, 0));
+        assert!(Version::new(1, 0, 0) < Version::new(1, 1, 0));
+        assert!(Version::new(1, 0, 0) < Version::new(1, 0, 1));
+
+        // a prerelease version has lower precedence than a normal version.
+        assert!(
+            Version::new_prerelease(1, 0, 0, vec!["alpha".parse().unwrap()]) < Version::new(1, 0, 0)
+        );
+
+        // prerelease identifiers are compared left-to-right.
+        assert!(
+            Version::new_prerelease(1, 0, 0, vec!["alpha".parse().unwrap()])
+                < Version::new_prerelease(1, 0, 0, vec!["beta".parse().unwrap()])
+        );
+        assert!(
+            Version::new_prerelease(1, 0, 0, vec![1.into()])
+                < Version::new_prerelease(1, 0, 0, vec![2.into()])
+        );
+
+        // when all shared fields are equal, more fields ranks higher.
+        assert!(
+            Version::new_prerelease(1, 0, 0, vec!["alpha".parse().unwrap()])
+                < Version::new_prerelease(
+                    1,
+                    0,
+                    0,
+                    vec!["alpha".parse().unwrap(), 1.into()]
+                )
+        );
+
+        // build metadata is ignored for ordering.
+        assert_eq!(
+            Version::new_build(1, 0, 0, vec!["a".parse().unwrap()])
+                .cmp(&Version::new_build(1, 0, 0, vec!["b".parse().unwrap()])),
+            Ordering::Equal
+        );
+        assert_ne!(
+            Version::new_build(1, 0, 0, vec!["a".parse().unwrap()]),
+            Version::new_build(1, 0, 0, vec!["b".parse().unwrap()])
+        );
+        assert!(Version::new_build(1, 0, 0, vec!["a".parse().unwrap()])
+            .eq_precedence(&Version::new_build(1, 0, 0, vec!["b".parse().unwrap()])));
+    }
 }